@@ -1,6 +1,8 @@
+pub mod ast_expression;
 pub mod reverse_polish_notation;
 use std::fmt;
 
+pub use ast_expression::AstExpression;
 pub use reverse_polish_notation::ReversePolishNotation;
 
 #[derive(PartialEq, Debug)]
@@ -17,18 +19,23 @@ impl fmt::Display for ParsingError {
                 "Parentheses must match. ) can not come before (. Count of ( must equal to )",
             ),
             ParsingError::InvalidInput => {
-                write!(f, "Enter valid mathematical infix notation. Valid symbols are: + / - () and integer digits",)
+                write!(f, "Enter valid mathematical infix notation. Valid symbols are: + / - * ^ % & | ~ () and numbers (decimal, scientific, 0x hex or 0b binary)",)
             }
         }
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Operator {
     Add,
     Substract,
     Divide,
     Multiply,
+    Power,
+    Modulo,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
     LeftParenthesis,
     RightParenthesis,
 }
@@ -43,32 +50,56 @@ impl TryFrom<char> for Operator {
             '-' => Operator::Substract,
             '*' => Operator::Multiply,
             '/' => Operator::Divide,
+            '^' => Operator::Power,
+            '%' => Operator::Modulo,
+            '&' => Operator::BitwiseAnd,
+            '|' => Operator::BitwiseOr,
+            // '^' is already Power, so bitwise xor gets the next free symbol.
+            '~' => Operator::BitwiseXor,
             _ => return Err(ParsingError::InvalidInput),
         })
     }
 }
 
+#[derive(PartialEq, Debug)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
 impl Operator {
     fn precedence(&self) -> u8 {
         match self {
-            Self::Add => 1,
-            Self::Substract => 1,
-            Self::Divide => 2,
-            Self::Multiply => 2,
+            Self::BitwiseOr => 1,
+            Self::BitwiseXor => 2,
+            Self::BitwiseAnd => 3,
+            Self::Add => 4,
+            Self::Substract => 4,
+            Self::Divide => 5,
+            Self::Multiply => 5,
+            Self::Modulo => 5,
+            Self::Power => 6,
             Self::LeftParenthesis => 0,
             Self::RightParenthesis => 0,
         }
     }
+
+    fn associativity(&self) -> Associativity {
+        match self {
+            Self::Power => Associativity::Right,
+            _ => Associativity::Left,
+        }
+    }
 }
 
 #[derive(Debug)]
 enum Token {
-    Operand(i32),
+    Operand(f32),
     Operator(Operator),
 }
 
-impl From<i32> for Token {
-    fn from(value: i32) -> Self {
+impl From<f32> for Token {
+    fn from(value: f32) -> Self {
         Token::Operand(value)
     }
 }
@@ -79,31 +110,143 @@ impl From<Operator> for Token {
     }
 }
 
+#[derive(PartialEq, Debug)]
+pub enum CalcError {
+    DivideByZero,
+    MalformedExpression,
+    NonIntegerBitwise,
+    UnknownBase,
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalcError::DivideByZero => write!(f, "Can not divide by zero",),
+            CalcError::MalformedExpression => {
+                write!(f, "Expression is malformed and can not be evaluated",)
+            }
+            CalcError::NonIntegerBitwise => {
+                write!(f, "Bitwise operators require whole-number operands",)
+            }
+            CalcError::UnknownBase => write!(f, "Base must be between 2 and 36",),
+        }
+    }
+}
+
 pub trait Calculate {
-    fn calculate(&self) -> f32;
+    fn calculate(&self) -> Result<f32, CalcError>;
+}
+
+const RADIX_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Renders `value` in the given `base` (2-36), falling back to decimal formatting for
+/// non-integer results. Integer results are converted via repeated division, the same way
+/// `parse_number` reads hex/binary literals in reverse.
+pub fn format_result(value: f32, base: u32) -> Result<String, CalcError> {
+    if !(2..=36).contains(&base) {
+        return Err(CalcError::UnknownBase);
+    }
+
+    if value.fract() != 0.0 {
+        return Ok(value.to_string());
+    }
+
+    let mut magnitude = (value as i64).unsigned_abs();
+    if magnitude == 0 {
+        return Ok("0".to_string());
+    }
+
+    let mut digits = Vec::new();
+    while magnitude > 0 {
+        digits.push(RADIX_DIGITS[(magnitude % base as u64) as usize]);
+        magnitude /= base as u64;
+    }
+
+    if value < 0.0 {
+        digits.push(b'-');
+    }
+    digits.reverse();
+
+    Ok(String::from_utf8(digits).expect("radix digits are ASCII"))
 }
 
 // Functions bellow are performed on raw input, therefore they could be used with other parsers.
 
-pub fn parse_number(value: &str) -> Result<i32, ParsingError> {
+pub fn parse_number(value: &str) -> Result<f32, ParsingError> {
     let mut value = value;
-    let mut multiplicator = 1;
+    let mut multiplicator = 1.0;
 
-    if value.contains('-') {
+    if value.starts_with('-') {
         value = &value[1..];
-        multiplicator = -1;
+        multiplicator = -1.0;
     }
 
     if value.is_empty() {
         return Err(ParsingError::InvalidInput);
     }
 
+    if let Some(hex_digits) = value.strip_prefix("0x") {
+        let parsed = i32::from_str_radix(hex_digits, 16).map_err(|_| ParsingError::InvalidInput)?;
+        return Ok(parsed as f32 * multiplicator);
+    }
+
+    if let Some(binary_digits) = value.strip_prefix("0b") {
+        let parsed =
+            i32::from_str_radix(binary_digits, 2).map_err(|_| ParsingError::InvalidInput)?;
+        return Ok(parsed as f32 * multiplicator);
+    }
+
     Ok(value
-        .parse::<i32>()
+        .parse::<f32>()
         .map_err(|_| ParsingError::InvalidInput)?)
     .map(|value| value * multiplicator)
 }
 
+/// Converts a value into an `i32`, rejecting fractional operands for the bitwise operators.
+/// Shared by every `Calculate` evaluator since they all apply the same `i32`-only operators.
+pub fn as_integer(value: f32) -> Result<i32, CalcError> {
+    if value.fract() != 0.0 {
+        return Err(CalcError::NonIntegerBitwise);
+    }
+    Ok(value as i32)
+}
+
+/// Decides whether `current_char` extends the number literal accumulated so far in
+/// `parsing_number`, covering decimal points, scientific notation and the `0x`/`0b` prefixes.
+pub fn is_number_char(current_char: char, parsing_number: &str) -> bool {
+    let digits_so_far = parsing_number.strip_prefix('-').unwrap_or(parsing_number);
+
+    if current_char.is_ascii_digit() {
+        return true;
+    }
+
+    // Once we're past the `0x` prefix, every hex digit (including `b`/`e`) is a literal
+    // digit, not the `0b` prefix or a scientific-notation `e` — check this before those.
+    if digits_so_far.starts_with("0x") {
+        return current_char.is_ascii_hexdigit();
+    }
+
+    if current_char == '.' {
+        return !digits_so_far.is_empty() && !digits_so_far.contains('.');
+    }
+
+    if current_char == 'x' || current_char == 'b' {
+        return digits_so_far == "0";
+    }
+
+    if current_char == 'e' {
+        return !digits_so_far.is_empty()
+            && !digits_so_far.contains('e')
+            && !digits_so_far.starts_with("0b");
+    }
+
+    if current_char == '-' {
+        return digits_so_far.ends_with('e');
+    }
+
+    false
+}
+
 fn evaluate_parenthes_match(value: &str) -> Result<(), ParsingError> {
     let mut parentheses_sum = 0;
     for char in value.chars() {
@@ -125,8 +268,13 @@ fn evaluate_parenthes_match(value: &str) -> Result<(), ParsingError> {
 }
 
 fn check_char_validity(char: char) -> Result<(), ParsingError> {
-    let valid_chars_except_for_numbers = [')', '(', '+', '*', '-', '/'];
-    if !char.is_ascii_digit() && !valid_chars_except_for_numbers.contains(&char) {
+    let valid_chars_except_for_numbers =
+        [')', '(', '+', '*', '-', '/', '^', '%', '&', '|', '~'];
+    if !char.is_ascii_hexdigit()
+        && char != '.'
+        && char != 'x'
+        && !valid_chars_except_for_numbers.contains(&char)
+    {
         return Err(ParsingError::InvalidInput);
     }
     Ok(())
@@ -145,6 +293,13 @@ fn validate_infix_notation(input: &str) -> Result<(), ParsingError> {
         || input.ends_with('+')
         || input.ends_with('*')
         || input.ends_with('/')
+        || input.ends_with('^')
+        || input.ends_with('%')
+        || input.ends_with('&')
+        || input.ends_with('|')
+        || input.ends_with('~')
+        || input.ends_with('.')
+        || input.ends_with('x')
     {
         return Err(ParsingError::InvalidInput);
     }
@@ -153,6 +308,12 @@ fn validate_infix_notation(input: &str) -> Result<(), ParsingError> {
         || input.starts_with('+')
         || input.starts_with('*')
         || input.starts_with('/')
+        || input.starts_with('^')
+        || input.starts_with('%')
+        || input.starts_with('&')
+        || input.starts_with('|')
+        || input.starts_with('~')
+        || input.starts_with('.')
     {
         return Err(ParsingError::InvalidInput);
     }
@@ -164,7 +325,8 @@ fn validate_infix_notation(input: &str) -> Result<(), ParsingError> {
     evaluate_parenthes_match(input)?;
 
     let mut previous_char_option: Option<char> = None;
-    let chars_which_come_after_digit_or_closing_parenthesis = [')', '+', '*', '-', '/'];
+    let chars_which_come_after_digit_or_closing_parenthesis =
+        [')', '+', '*', '-', '/', '^', '%', '&', '|', '~'];
 
     for current_char in input.chars() {
         check_char_validity(current_char)?;
@@ -189,7 +351,12 @@ fn validate_infix_notation(input: &str) -> Result<(), ParsingError> {
                 || previous_char == '+'
                 || previous_char == '-'
                 || previous_char == '*'
-                || previous_char == '/')
+                || previous_char == '/'
+                || previous_char == '^'
+                || previous_char == '%'
+                || previous_char == '&'
+                || previous_char == '|'
+                || previous_char == '~')
         {
             return Err(ParsingError::InvalidInput);
         }
@@ -208,14 +375,14 @@ pub fn check_operator_char_order(
     current_char: char,
     previous_char: Option<char>,
 ) -> Result<(), ParsingError> {
-    if previous_char.is_none() && !current_char.is_numeric() && current_char != '(' {
+    if previous_char.is_none() && !current_char.is_ascii_hexdigit() && current_char != '(' {
         println!("returns 1");
         return Err(ParsingError::InvalidInput);
     }
 
     if current_char == '(' {
         if previous_char
-            .is_some_and(|previous_char| previous_char == ')' || previous_char.is_numeric())
+            .is_some_and(|previous_char| previous_char == ')' || previous_char.is_ascii_hexdigit())
         {
             println!("returns 1.1");
             return Err(ParsingError::InvalidInput);
@@ -225,7 +392,7 @@ pub fn check_operator_char_order(
     }
 
     if let Some(previous_char) = previous_char {
-        if !previous_char.is_numeric() && previous_char != ')' {
+        if !previous_char.is_ascii_hexdigit() && previous_char != ')' {
             println!("returns 2");
             return Err(ParsingError::InvalidInput);
         }
@@ -247,9 +414,52 @@ pub fn is_minus_unary_operator(current_char: char, previous_char: &Option<char>)
         return true;
     };
 
-    if previous_char.is_numeric() || previous_char == &')' {
+    if previous_char.is_ascii_hexdigit() || previous_char == &')' {
         return false;
     }
 
     true
 }
+
+/// Scans an already whitespace-stripped, validated infix string into a flat token stream.
+/// Shared by `ReversePolishNotation`'s shunting-yard pass and `AstExpression`'s Pratt parser,
+/// which each turn the same tokens into a different expression representation.
+fn tokenize(input: &str) -> Result<Vec<Token>, ParsingError> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut parsing_number = String::new();
+    let mut previous_char = None;
+    let mut is_next_expression_negative = false;
+
+    for current_char in input.chars() {
+        if is_minus_unary_operator(current_char, &previous_char) {
+            is_next_expression_negative = true;
+            continue;
+        }
+
+        if is_number_char(current_char, &parsing_number) {
+            if is_next_expression_negative {
+                parsing_number.push('-');
+                is_next_expression_negative = false;
+            }
+
+            parsing_number.push(current_char);
+            previous_char = Some(current_char);
+            continue;
+        }
+
+        if !parsing_number.is_empty() {
+            tokens.push(Token::from(parse_number(parsing_number.as_str())?));
+            parsing_number.clear();
+        }
+
+        check_operator_char_order(current_char, previous_char)?;
+        previous_char = Some(current_char);
+        tokens.push(Token::from(Operator::try_from(current_char)?));
+    }
+
+    if !parsing_number.is_empty() {
+        tokens.push(Token::from(parse_number(parsing_number.as_str())?));
+    }
+
+    Ok(tokens)
+}
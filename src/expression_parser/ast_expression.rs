@@ -0,0 +1,235 @@
+use crate::expression_parser::{CalcError, Calculate};
+
+use super::{
+    as_integer, tokenize, validate_infix_notation, Associativity, Operator, ParsingError, Token,
+};
+
+#[derive(Debug)]
+enum Expr {
+    Num(f32),
+    Unary(Operator, Box<Expr>),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+const UNARY_MINUS_BINDING_POWER: u8 = 100;
+
+fn binding_power(operator: &Operator) -> (u8, u8) {
+    let precedence = operator.precedence() * 10;
+    match operator.associativity() {
+        Associativity::Left => (precedence, precedence + 1),
+        Associativity::Right => (precedence, precedence),
+    }
+}
+
+/**
+ * Pratt (precedence-climbing) parser: parse_expr reads one primary, then
+ * keeps consuming infix operators whose left binding power is at least
+ * min_binding_power, recursing with the operator's right binding power.
+ * Right-associative operators recurse with the same binding power they were
+ * read at, so a chain like `2^3^2` groups to the right.
+ */
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek_operator(&self) -> Option<Operator> {
+        match self.tokens.get(self.position) {
+            Some(Token::Operator(operator)) => Some(*operator),
+            _ => None,
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParsingError> {
+        match self.tokens.get(self.position) {
+            Some(Token::Operand(value)) => {
+                let value = *value;
+                self.position += 1;
+                Ok(Expr::Num(value))
+            }
+            Some(Token::Operator(Operator::Substract)) => {
+                self.position += 1;
+                let operand = self.parse_expr(UNARY_MINUS_BINDING_POWER)?;
+                Ok(Expr::Unary(Operator::Substract, Box::new(operand)))
+            }
+            Some(Token::Operator(Operator::LeftParenthesis)) => {
+                self.position += 1;
+                let expr = self.parse_expr(0)?;
+                match self.tokens.get(self.position) {
+                    Some(Token::Operator(Operator::RightParenthesis)) => {
+                        self.position += 1;
+                        Ok(expr)
+                    }
+                    _ => Err(ParsingError::ParenthesesNotMatching),
+                }
+            }
+            _ => Err(ParsingError::InvalidInput),
+        }
+    }
+
+    fn parse_expr(&mut self, min_binding_power: u8) -> Result<Expr, ParsingError> {
+        let mut lhs = self.parse_primary()?;
+
+        while let Some(operator) = self.peek_operator() {
+            if operator == Operator::RightParenthesis {
+                break;
+            }
+
+            let (left_binding_power, right_binding_power) = binding_power(&operator);
+            if left_binding_power < min_binding_power {
+                break;
+            }
+
+            self.position += 1;
+            let rhs = self.parse_expr(right_binding_power)?;
+            lhs = Expr::Binary(Box::new(lhs), operator, Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+}
+
+#[derive(Debug)]
+pub struct AstExpression {
+    root: Expr,
+}
+
+impl TryFrom<String> for AstExpression {
+    type Error = ParsingError;
+
+    fn try_from(input: String) -> Result<Self, ParsingError> {
+        let input: String = input.split_whitespace().collect();
+        validate_infix_notation(&input)?;
+        let tokens = tokenize(&input)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let root = parser.parse_expr(0)?;
+
+        if parser.position != parser.tokens.len() {
+            return Err(ParsingError::InvalidInput);
+        }
+
+        Ok(Self { root })
+    }
+}
+
+fn evaluate(expr: &Expr) -> Result<f32, CalcError> {
+    match expr {
+        Expr::Num(value) => Ok(*value),
+        Expr::Unary(Operator::Substract, operand) => Ok(-evaluate(operand)?),
+        Expr::Unary(_, _) => Err(CalcError::MalformedExpression),
+        Expr::Binary(lhs, operator, rhs) => {
+            let lhs = evaluate(lhs)?;
+            let rhs = evaluate(rhs)?;
+            match operator {
+                Operator::Add => Ok(lhs + rhs),
+                Operator::Substract => Ok(lhs - rhs),
+                Operator::Multiply => Ok(lhs * rhs),
+                Operator::Divide => {
+                    if rhs == 0.0 {
+                        return Err(CalcError::DivideByZero);
+                    }
+                    Ok(lhs / rhs)
+                }
+                Operator::Power => Ok(lhs.powf(rhs)),
+                Operator::Modulo => {
+                    if rhs == 0.0 {
+                        return Err(CalcError::DivideByZero);
+                    }
+                    Ok(lhs % rhs)
+                }
+                Operator::BitwiseAnd => Ok((as_integer(lhs)? & as_integer(rhs)?) as f32),
+                Operator::BitwiseOr => Ok((as_integer(lhs)? | as_integer(rhs)?) as f32),
+                Operator::BitwiseXor => Ok((as_integer(lhs)? ^ as_integer(rhs)?) as f32),
+                _ => Err(CalcError::MalformedExpression),
+            }
+        }
+    }
+}
+
+impl Calculate for AstExpression {
+    fn calculate(&self) -> Result<f32, CalcError> {
+        evaluate(&self.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ast_expression_respects_operator_precedence() -> Result<(), ParsingError> {
+        let expression = String::from("2+3*4");
+        assert_eq!(
+            AstExpression::try_from(expression)?.calculate().unwrap(),
+            14.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ast_expression_with_parentheses() -> Result<(), ParsingError> {
+        let expression = String::from("(2+3)*4");
+        assert_eq!(
+            AstExpression::try_from(expression)?.calculate().unwrap(),
+            20.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ast_expression_power_is_right_associative() -> Result<(), ParsingError> {
+        let expression = String::from("2^3^2");
+        assert_eq!(
+            AstExpression::try_from(expression)?.calculate().unwrap(),
+            512.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ast_expression_with_unary_minus() -> Result<(), ParsingError> {
+        let expression = String::from("-3+5");
+        assert_eq!(
+            AstExpression::try_from(expression)?.calculate().unwrap(),
+            2.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ast_expression_with_modulo() -> Result<(), ParsingError> {
+        let expression = String::from("7%3");
+        assert_eq!(
+            AstExpression::try_from(expression)?.calculate().unwrap(),
+            1.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ast_expression_with_bitwise_operators() -> Result<(), ParsingError> {
+        let expression = String::from("6&3|1~2");
+        assert_eq!(
+            AstExpression::try_from(expression)?.calculate().unwrap(),
+            ((6 & 3) | (1 ^ 2)) as f32
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ast_expression_invalid_symbol() {
+        let invalid_expression = String::from("{2+3}");
+        assert!(AstExpression::try_from(invalid_expression)
+            .is_err_and(|e| e == ParsingError::InvalidInput,),);
+    }
+}
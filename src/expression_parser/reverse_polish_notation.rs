@@ -1,26 +1,9 @@
-use crate::expression_parser::Calculate;
+use crate::expression_parser::{CalcError, Calculate};
 
 use super::{
-    check_operator_char_order, is_minus_unary_operator, parse_number, validate_infix_notation,
-    Operator, ParsingError, Token,
+    as_integer, tokenize, validate_infix_notation, Associativity, Operator, ParsingError, Token,
 };
 
-static INVALID_RPN_PANIC_ERROR: &str = "Invalid RPN";
-
-fn push_number_to_rpn_container(
-    rpn_container: &mut Vec<Token>,
-    parsing_number: &mut String,
-) -> Result<(), ParsingError> {
-    if parsing_number.is_empty() {
-        return Ok(());
-    }
-
-    rpn_container.push(Token::from(parse_number(parsing_number.as_str())?));
-
-    parsing_number.clear();
-    Ok(())
-}
-
 #[derive(Default, Debug)]
 pub struct ReversePolishNotation {
     tokens: Vec<Token>,
@@ -36,34 +19,19 @@ impl TryFrom<String> for ReversePolishNotation {
     fn try_from(input: String) -> Result<Self, ParsingError> {
         let input: String = input.split_whitespace().collect();
         validate_infix_notation(&input)?;
-        let mut parsing_number = String::new();
-        let mut previous_char = None;
+
         let mut operator_stack: Vec<Operator> = Vec::new();
         let mut rpn_container: Vec<Token> = Vec::new();
-        let mut is_next_expression_negative = false;
 
-        for current_char in input.chars() {
-            if is_minus_unary_operator(current_char, &previous_char) {
-                is_next_expression_negative = true;
-                continue;
-            };
-
-            if current_char.is_numeric() {
-                if is_next_expression_negative {
-                    parsing_number.push('-');
-                    is_next_expression_negative = false;
+        for token in tokenize(&input)? {
+            let current_operator = match token {
+                Token::Operand(_) => {
+                    rpn_container.push(token);
+                    continue;
                 }
+                Token::Operator(operator) => operator,
+            };
 
-                parsing_number.push(current_char);
-                previous_char = Some(current_char);
-                continue;
-            }
-
-            push_number_to_rpn_container(&mut rpn_container, &mut parsing_number)?;
-            check_operator_char_order(current_char, previous_char)?;
-            previous_char = Some(current_char);
-
-            let current_operator = Operator::try_from(current_char)?;
             let last_stack_operator = operator_stack.last();
 
             if current_operator == Operator::LeftParenthesis {
@@ -99,7 +67,16 @@ impl TryFrom<String> for ReversePolishNotation {
                 continue;
             } else {
                 while let Some(previous_operator_on_top) = operator_stack.pop() {
-                    if current_operator.precedence() <= previous_operator_on_top.precedence() {
+                    let should_pop = match current_operator.associativity() {
+                        Associativity::Left => {
+                            current_operator.precedence() <= previous_operator_on_top.precedence()
+                        }
+                        Associativity::Right => {
+                            current_operator.precedence() < previous_operator_on_top.precedence()
+                        }
+                    };
+
+                    if should_pop {
                         rpn_container.push(previous_operator_on_top.into());
                         continue;
                     }
@@ -110,8 +87,6 @@ impl TryFrom<String> for ReversePolishNotation {
             }
         }
 
-        push_number_to_rpn_container(&mut rpn_container, &mut parsing_number)?;
-
         while let Some(last_stack_operator) = operator_stack.pop() {
             rpn_container.push(last_stack_operator.into());
         }
@@ -122,29 +97,105 @@ impl TryFrom<String> for ReversePolishNotation {
     }
 }
 
-impl Calculate for ReversePolishNotation {
-    fn calculate(&self) -> f32 {
-        let mut value_stack: Vec<f32> = Vec::new();
-        for token in self.tokens.iter() {
-            match token {
-                Token::Operand(value) => value_stack.push(*value as f32),
-                Token::Operator(operator) => {
-                    let value_1 = value_stack.pop().expect(INVALID_RPN_PANIC_ERROR);
-                    let value_2 = value_stack.pop().expect(INVALID_RPN_PANIC_ERROR);
-
-                    let result = match operator {
-                        Operator::Add => value_1 + value_2,
-                        Operator::Substract => value_2 - value_1,
-                        Operator::Multiply => value_1 * value_2,
-                        Operator::Divide => value_2 / value_1,
-                        _ => panic!("{}", INVALID_RPN_PANIC_ERROR),
+#[derive(Debug, PartialEq)]
+pub enum OpCode {
+    PushConst(f32),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Mod,
+    BitAnd,
+    BitOr,
+    BitXor,
+}
+
+impl ReversePolishNotation {
+    /// Lowers the already shunting-yarded tokens into a flat bytecode program for the `Vm`.
+    pub fn compile(&self) -> Vec<OpCode> {
+        self.tokens
+            .iter()
+            .map(|token| match token {
+                Token::Operand(value) => OpCode::PushConst(*value),
+                Token::Operator(Operator::Add) => OpCode::Add,
+                Token::Operator(Operator::Substract) => OpCode::Sub,
+                Token::Operator(Operator::Multiply) => OpCode::Mul,
+                Token::Operator(Operator::Divide) => OpCode::Div,
+                Token::Operator(Operator::Power) => OpCode::Pow,
+                Token::Operator(Operator::Modulo) => OpCode::Mod,
+                Token::Operator(Operator::BitwiseAnd) => OpCode::BitAnd,
+                Token::Operator(Operator::BitwiseOr) => OpCode::BitOr,
+                Token::Operator(Operator::BitwiseXor) => OpCode::BitXor,
+                Token::Operator(Operator::LeftParenthesis | Operator::RightParenthesis) => {
+                    unreachable!("parentheses are consumed by the shunting-yard algorithm")
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct Vm {
+    stack: Vec<f32>,
+}
+
+impl Vm {
+    pub fn run(&mut self, code: &[OpCode]) -> Result<f32, CalcError> {
+        for op_code in code {
+            match op_code {
+                OpCode::PushConst(value) => self.stack.push(*value),
+                OpCode::Add | OpCode::Sub | OpCode::Mul | OpCode::Div | OpCode::Pow
+                | OpCode::Mod => {
+                    let value_1 = self.stack.pop().ok_or(CalcError::MalformedExpression)?;
+                    let value_2 = self.stack.pop().ok_or(CalcError::MalformedExpression)?;
+
+                    let result = match op_code {
+                        OpCode::Add => value_1 + value_2,
+                        OpCode::Sub => value_2 - value_1,
+                        OpCode::Mul => value_1 * value_2,
+                        OpCode::Div => {
+                            if value_1 == 0.0 {
+                                return Err(CalcError::DivideByZero);
+                            }
+                            value_2 / value_1
+                        }
+                        OpCode::Pow => value_2.powf(value_1),
+                        OpCode::Mod => {
+                            if value_1 == 0.0 {
+                                return Err(CalcError::DivideByZero);
+                            }
+                            value_2 % value_1
+                        }
+                        OpCode::PushConst(_)
+                        | OpCode::BitAnd
+                        | OpCode::BitOr
+                        | OpCode::BitXor => unreachable!(),
+                    };
+                    self.stack.push(result);
+                }
+                OpCode::BitAnd | OpCode::BitOr | OpCode::BitXor => {
+                    let value_1 = as_integer(self.stack.pop().ok_or(CalcError::MalformedExpression)?)?;
+                    let value_2 = as_integer(self.stack.pop().ok_or(CalcError::MalformedExpression)?)?;
+
+                    let result = match op_code {
+                        OpCode::BitAnd => value_2 & value_1,
+                        OpCode::BitOr => value_2 | value_1,
+                        OpCode::BitXor => value_2 ^ value_1,
+                        _ => unreachable!(),
                     };
-                    value_stack.push(result);
+                    self.stack.push(result as f32);
                 }
             }
         }
 
-        value_stack.pop().expect(INVALID_RPN_PANIC_ERROR)
+        self.stack.pop().ok_or(CalcError::MalformedExpression)
+    }
+}
+
+impl Calculate for ReversePolishNotation {
+    fn calculate(&self) -> Result<f32, CalcError> {
+        Vm::default().run(&self.compile())
     }
 }
 
@@ -205,7 +256,9 @@ mod tests {
     fn test_valid_expression() -> Result<(), ParsingError> {
         let valid_expression = String::from("-3+5/5*(10-3/3)-6");
         assert_eq!(
-            ReversePolishNotation::try_from(valid_expression)?.calculate(),
+            ReversePolishNotation::try_from(valid_expression)?
+                .calculate()
+                .unwrap(),
             0 as f32
         );
 
@@ -216,7 +269,9 @@ mod tests {
     fn test_valid_expression_with_multiple_parentheses() -> Result<(), ParsingError> {
         let valid_expression = String::from("((-3+5/5*(((10-3/3)))-6))");
         assert_eq!(
-            ReversePolishNotation::try_from(valid_expression)?.calculate(),
+            ReversePolishNotation::try_from(valid_expression)?
+                .calculate()
+                .unwrap(),
             0 as f32
         );
 
@@ -227,7 +282,9 @@ mod tests {
     fn test_valid_expression_with_multiple_white_space() -> Result<(), ParsingError> {
         let valid_expression = String::from("-3     +    501/501*(    ((10-3/3)))   -6");
         assert_eq!(
-            ReversePolishNotation::try_from(valid_expression)?.calculate(),
+            ReversePolishNotation::try_from(valid_expression)?
+                .calculate()
+                .unwrap(),
             0 as f32
         );
 
@@ -238,21 +295,209 @@ mod tests {
     fn test_valid_expression_with_value_in_parentheses() -> Result<(), ParsingError> {
         let valid_expression = String::from("-3     +    5/(5)*(    ((10-3/3)))   -(6)");
         assert_eq!(
-            ReversePolishNotation::try_from(valid_expression)?.calculate(),
+            ReversePolishNotation::try_from(valid_expression)?
+                .calculate()
+                .unwrap(),
             0 as f32
         );
 
         Ok(())
     }
 
+    #[test]
+    fn test_power_operator_is_right_associative() -> Result<(), ParsingError> {
+        let expression = String::from("2^3^2");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            512.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_operator_binds_tighter_than_multiplication() -> Result<(), ParsingError> {
+        let expression = String::from("2*3^2");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            18.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_expression_with_decimal_literal() -> Result<(), ParsingError> {
+        let expression = String::from("3.14+0.86");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            4.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_expression_with_scientific_notation() -> Result<(), ParsingError> {
+        let expression = String::from("1.5e-3*1000");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            1.5
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_expression_with_hex_literal() -> Result<(), ParsingError> {
+        let expression = String::from("0xFF+1");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            256.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_expression_with_binary_literal() -> Result<(), ParsingError> {
+        let expression = String::from("0b1010+2");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            12.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_expression_with_hex_literal_containing_b_and_e_digits() -> Result<(), ParsingError>
+    {
+        let expression = String::from("0x1b+1");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            28.0
+        );
+
+        let expression = String::from("0xae+1");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            175.0
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_valid_expression_with_just_a_number() -> Result<(), ParsingError> {
         let valid_expression = String::from("(((-1000)))");
         assert_eq!(
-            ReversePolishNotation::try_from(valid_expression)?.calculate(),
+            ReversePolishNotation::try_from(valid_expression)?
+                .calculate()
+                .unwrap(),
             -1000.0
         );
 
         Ok(())
     }
+
+    #[test]
+    fn test_compile_lowers_tokens_to_opcodes() -> Result<(), ParsingError> {
+        let expression = String::from("3+4");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?.compile(),
+            vec![
+                OpCode::PushConst(3.0),
+                OpCode::PushConst(4.0),
+                OpCode::Add,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vm_runs_compiled_opcodes() {
+        let code = vec![
+            OpCode::PushConst(3.0),
+            OpCode::PushConst(4.0),
+            OpCode::Add,
+        ];
+        assert_eq!(Vm::default().run(&code).unwrap(), 7.0);
+    }
+
+    #[test]
+    fn test_valid_expression_with_modulo() -> Result<(), ParsingError> {
+        let expression = String::from("7%3");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            1.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_expression_with_bitwise_operators() -> Result<(), ParsingError> {
+        let expression = String::from("6&3|1~2");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?
+                .calculate()
+                .unwrap(),
+            ((6 & 3) | (1 ^ 2)) as f32
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_returns_non_integer_bitwise_error() -> Result<(), ParsingError> {
+        let expression = String::from("1.5&1");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?.calculate(),
+            Err(CalcError::NonIntegerBitwise)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_returns_modulo_by_zero_error() -> Result<(), ParsingError> {
+        let expression = String::from("4%0");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?.calculate(),
+            Err(CalcError::DivideByZero)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_returns_divide_by_zero_error() -> Result<(), ParsingError> {
+        let expression = String::from("1/0");
+        assert_eq!(
+            ReversePolishNotation::try_from(expression)?.calculate(),
+            Err(CalcError::DivideByZero)
+        );
+
+        Ok(())
+    }
 }
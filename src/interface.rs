@@ -1,10 +1,11 @@
-use crate::expression_parser::{Calculate, ParsingError, ReversePolishNotation};
+use crate::expression_parser::{format_result, CalcError, Calculate, ParsingError};
+use std::fmt::Debug;
 use std::io::{stdin, stdout, Write};
 
-pub fn get_expression_from_user_input<T>() -> Result<T, ParsingError>
-where
-    T: Calculate + TryFrom<String, Error = ParsingError>,
-{
+const BASE_COMMAND_PREFIX: &str = ":base ";
+const DEFAULT_BASE: u32 = 10;
+
+fn read_user_input() -> Result<String, ParsingError> {
     println!("Press Ctrl+C to exit");
     print!("Please enter expression and enter to calculate: ");
     let _ = stdout().flush();
@@ -12,15 +13,60 @@ where
     stdin()
         .read_line(&mut input)
         .map_err(|_| ParsingError::InvalidInput)?;
+    Ok(input)
+}
+
+pub fn get_expression_from_user_input<T>(input: String) -> Result<T, ParsingError>
+where
+    T: Calculate + TryFrom<String, Error = ParsingError>,
+{
     T::try_from(input)
 }
 
-pub fn run_interface() {
+/// Parses a `:base <n>` REPL command, returning the requested radix if the input matches.
+fn parse_base_command(input: &str) -> Option<u32> {
+    input
+        .trim()
+        .strip_prefix(BASE_COMMAND_PREFIX)
+        .and_then(|base| base.trim().parse::<u32>().ok())
+}
+
+/// Runs the REPL loop against any parser/evaluator pair, e.g.
+/// `run_interface::<ReversePolishNotation>()` or `run_interface::<AstExpression>()`.
+/// Accepts a `:base <n>` command (2-36) that switches how results are printed afterwards.
+pub fn run_interface<T>()
+where
+    T: Calculate + TryFrom<String, Error = ParsingError> + Debug,
+{
+    let mut base = DEFAULT_BASE;
+
     loop {
-        match get_expression_from_user_input::<ReversePolishNotation>() {
+        let input = match read_user_input() {
+            Ok(input) => input,
+            Err(parsing_error) => {
+                println!("{}", parsing_error);
+                continue;
+            }
+        };
+
+        if let Some(requested_base) = parse_base_command(&input) {
+            if !(2..=36).contains(&requested_base) {
+                println!("{}", CalcError::UnknownBase);
+                continue;
+            }
+
+            base = requested_base;
+            println!("Output base set to {}", base);
+            continue;
+        }
+
+        match get_expression_from_user_input::<T>(input) {
             Ok(expression) => {
                 println!("Notation: {:?}", expression);
-                println!("Result: {}", expression.calculate())
+                match expression.calculate().and_then(|value| format_result(value, base)) {
+                    Ok(result) => println!("Result: {}", result),
+                    Err(calc_error) => println!("{}", calc_error),
+                }
             }
             Err(parsing_error) => println!("{}", parsing_error),
         }